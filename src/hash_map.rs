@@ -1,73 +1,807 @@
 use std::{
     alloc::{self, Layout},
-    mem
+    cell::Cell,
+    hash::{BuildHasher, Hash, Hasher},
+    iter::FusedIterator,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    slice,
+    time::{SystemTime, UNIX_EPOCH}
 };
 
 /// An error that may be due to insertion of duplicate key.
 #[derive(Debug)]
-pub struct DupErr {
-    pub key: i32
+pub struct DupErr<K> {
+    pub key: K
+}
+
+/// An error returned by the `try_*` family of constructors and [`HashMap::try_reserve`] when the
+/// backing table could not be allocated.
+#[derive(Debug)]
+pub enum TryReserveError {
+    /// The requested capacity, or the allocation it implies, overflowed `usize`.
+    CapacityOverflow,
+    /// The allocator returned an error.
+    AllocError {
+        /// The layout that allocation was attempted with.
+        layout: Layout
+    }
+}
+
+/// The internal, pre-keying state of a round of SipHash.
+#[derive(Clone, Copy)]
+struct SipState {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64
+}
+
+impl SipState {
+    fn new(k0: u64, k1: u64) -> SipState {
+        SipState {
+            v0: k0 ^ 0x736f_6d65_7073_6575,
+            v1: k1 ^ 0x646f_7261_6e64_6f6d,
+            v2: k0 ^ 0x6c79_6765_6e65_7261,
+            v3: k1 ^ 0x7465_6462_7974_6573
+        }
+    }
+
+    #[inline]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, block: u64) {
+        self.v3 ^= block;
+        self.round();
+        self.v0 ^= block;
+    }
+
+    #[inline]
+    fn finish(mut self) -> u64 {
+        self.v2 ^= 0xff;
+        self.round();
+        self.round();
+        self.round();
+
+        self.v0 ^ self.v1 ^ self.v2 ^ self.v3
+    }
+}
+
+/// A keyed hasher built on SipHash-1-3 (one compression round per block, three finalization
+/// rounds): fast, and its output is unpredictable without the key pair it was built with, which
+/// is what keeps [`RandomState`] resistant to HashDoS.
+#[derive(Clone)]
+pub struct SipHasher13 {
+    state: SipState,
+    tail: [u8; 8],
+    ntail: usize,
+    length: usize
+}
+
+impl SipHasher13 {
+    fn new_with_keys(k0: u64, k1: u64) -> SipHasher13 {
+        SipHasher13 { state: SipState::new(k0, k1), tail: [0; 8], ntail: 0, length: 0 }
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length += bytes.len();
+
+        if self.ntail != 0 {
+            let fill = (8 - self.ntail).min(bytes.len());
+            self.tail[self.ntail..self.ntail + fill].copy_from_slice(&bytes[..fill]);
+            self.ntail += fill;
+            bytes = &bytes[fill..];
+
+            if self.ntail == 8 {
+                self.state.compress(u64::from_le_bytes(self.tail));
+                self.ntail = 0;
+            } else {
+                return;
+            }
+        }
+
+        while bytes.len() >= 8 {
+            let mut block = [0u8; 8];
+            block.copy_from_slice(&bytes[..8]);
+            self.state.compress(u64::from_le_bytes(block));
+            bytes = &bytes[8..];
+        }
+
+        self.ntail = bytes.len();
+        self.tail[..self.ntail].copy_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut last_block = [0u8; 8];
+        last_block[..self.ntail].copy_from_slice(&self.tail[..self.ntail]);
+        last_block[7] = self.length as u8;
+
+        let mut state = self.state;
+        state.compress(u64::from_le_bytes(last_block));
+        state.finish()
+    }
+}
+
+thread_local! {
+    /// Per-thread seed material for [`RandomState::new`]. Seeded once per thread from coarse
+    /// entropy, then perturbed on every call so repeated map construction stays cheap.
+    static THREAD_KEYS: Cell<(u64, u64)> = Cell::new(seed_keys());
+}
+
+/// Draws a starting key pair from coarse, readily-available entropy: wall-clock time and the
+/// address of a stack local (which ASLR randomizes per process). This is not a CSPRNG, but it is
+/// enough that an attacker crafting colliding keys offline cannot predict the slots they will
+/// land in on a given run.
+fn seed_keys() -> (u64, u64) {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let stack_marker = &nanos as *const u64 as u64;
+
+    let mut hasher = SipHasher13::new_with_keys(0x5bd1_e995_27d4_eb2f, 0x1656_67b1_9e37_79b9);
+    hasher.write_u64(nanos);
+    hasher.write_u64(stack_marker);
+    let k0 = hasher.finish();
+
+    hasher.write_u64(k0);
+    let k1 = hasher.finish();
+
+    (k0, k1)
+}
+
+/// Splits a single `u64` into a new, well-mixed one (the SplitMix64 finalizer), used to turn the
+/// thread-local counter into keys that don't resemble each other between calls.
+fn splitmix(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    x
+}
+
+/// The default [`BuildHasher`] for [`HashMap`]. Every `RandomState` is built from its own random
+/// pair of SipHash-1-3 keys, so the probe sequence an attacker-chosen key set produces cannot be
+/// predicted without knowing those keys — this is what protects the map from algorithmic-complexity
+/// (HashDoS) attacks that rely on forcing every key into one long probe chain.
+///
+/// Construction is cheap: the underlying entropy is only drawn once per thread ([`RandomState::new`]
+/// just perturbs and mixes a cached counter), so creating many short-lived maps is not a
+/// bottleneck.
+///
+/// The keys are not cryptographically secure randomness. For trusted, non-adversarial key sets
+/// where the DoS resistance isn't needed, build the map with a cheaper [`BuildHasher`] via
+/// [`HashMap::with_hasher`] instead.
+#[derive(Clone)]
+pub struct RandomState {
+    k0: u64,
+    k1: u64
+}
+
+impl RandomState {
+    /// Creates a new `RandomState` with a fresh pair of keys.
+    pub fn new() -> RandomState {
+        THREAD_KEYS.with(|keys| {
+            let (k0, k1) = keys.get();
+            let next = (k0.wrapping_add(0x9e37_79b9_7f4a_7c15), k1.wrapping_add(0xbf58_476d_1ce4_e5b9));
+            keys.set(next);
+
+            RandomState { k0: splitmix(next.0), k1: splitmix(next.1) }
+        })
+    }
+}
+
+impl Default for RandomState {
+    fn default() -> RandomState {
+        RandomState::new()
+    }
+}
+
+impl BuildHasher for RandomState {
+    type Hasher = SipHasher13;
+
+    fn build_hasher(&self) -> SipHasher13 {
+        SipHasher13::new_with_keys(self.k0, self.k1)
+    }
 }
 
 #[derive(Clone, Debug)]
-struct Item<V: Eq + Clone> {
-    key: i32,
-    value: V,
-    state: CellState
+struct Item<K, V: Eq + Clone> {
+    key: K,
+    value: V
+}
+
+/// Number of slots scanned together as one group, and the width of the `u64` a group's control
+/// bytes are loaded into. `MIN_CAPACITY` and every capacity reached by doubling it must stay a
+/// multiple of this so a group's control bytes never straddle the end of the array.
+const GROUP_SIZE: usize = 8;
+
+/// Control byte for a slot that has never held an item.
+const CTRL_EMPTY: u8 = 0xFF;
+/// Control byte for a slot whose item was removed. Kept distinct from `CTRL_EMPTY` so a probe
+/// can tell "nothing here, and nothing in any later group of this probe sequence" (stop once
+/// this group is checked) apart from "nothing here, but a match could still be further along"
+/// (keep scanning).
+const CTRL_DELETED: u8 = 0x80;
+
+/// Low bit of every byte lane in a `u64`, used by the SWAR byte-match trick.
+const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+/// High bit of every byte lane in a `u64`. `CTRL_EMPTY` and `CTRL_DELETED` both have it set;
+/// a full slot's control byte (its hash [`fragment`]) never does, since a fragment is `<= 0x7F`.
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+/// The low 7 bits of `hash`, stored in a slot's control byte once it holds an item: a cheap
+/// fingerprint that lets a probe reject most non-matching slots without touching the key.
+fn fragment(hash: u64) -> u8 {
+    (hash & 0x7f) as u8
+}
+
+/// `true` for a control byte that has an item (`CTRL_EMPTY`/`CTRL_DELETED` both have bit 7 set;
+/// a fragment byte never does).
+fn is_full(ctrl: u8) -> bool {
+    ctrl & 0x80 == 0
+}
+
+fn repeat_byte(b: u8) -> u64 {
+    (b as u64).wrapping_mul(LOW_BITS)
+}
+
+/// SWAR group match: a mask with bit 7 of lane `i` set wherever `group`'s `i`-th control byte
+/// equals `byte`, computed with one subtract-and-mask over all 8 lanes instead of a per-byte
+/// comparison. The borrow out of a matching lane can also set the bit of the lane directly above
+/// it when that lane's byte differs from `byte` only in bit 0; such a lane is never the lowest
+/// one flagged.
+fn match_byte(group: u64, byte: u8) -> u64 {
+    let xor = group ^ repeat_byte(byte);
+    xor.wrapping_sub(LOW_BITS) & !xor & HIGH_BITS
+}
+
+/// Iterates the lane indices (`0..GROUP_SIZE`) whose bit is set in a mask produced by
+/// [`match_byte`], lowest lane first.
+fn match_lanes(mut mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+
+        let lane = (mask.trailing_zeros() / 8) as usize;
+        mask &= !(0x80u64 << (lane * 8));
+        Some(lane)
+    })
+}
+
+/// The probe sequence over *groups* (triangular numbers modulo the group count), so repeated
+/// probes on a collision visit every group exactly once.
+struct ProbeSeq {
+    group: usize,
+    stride: usize
+}
+
+impl ProbeSeq {
+    fn new(seed: usize, group_mask: usize) -> ProbeSeq {
+        ProbeSeq { group: seed & group_mask, stride: 0 }
+    }
+
+    fn advance(&mut self, group_mask: usize) {
+        self.stride += 1;
+        self.group = (self.group + self.stride) & group_mask;
+    }
+}
+
+/// Minimum non-zero raw table size, in slots. Keeping this a power of two, and a multiple of
+/// `GROUP_SIZE`, lets every other power-of-two raw capacity be reached by doubling.
+const MIN_CAPACITY: usize = 32;
+
+/// Returns the number of items a table with `raw_capacity` slots can hold before it must grow,
+/// i.e. the load factor threshold (~87.5%, close to std's ~90.9% target).
+fn resize_at(raw_capacity: usize) -> usize {
+    raw_capacity - raw_capacity / 8
+}
+
+/// Picks the smallest power-of-two raw capacity (or zero) whose usable capacity covers
+/// `requested`, or an error if doubling there would overflow `usize` before covering it.
+fn raw_capacity_for(requested: usize) -> Result<usize, TryReserveError> {
+    if requested == 0 {
+        return Ok(0);
+    }
+
+    let mut raw = MIN_CAPACITY;
+    while resize_at(raw) < requested {
+        raw = raw.checked_mul(2).ok_or(TryReserveError::CapacityOverflow)?;
+    }
+    Ok(raw)
 }
 
-/// A hash map implemented with linear probing.
-pub struct HashMap<V: Eq + Clone> {
-    ht: Vec<Item<V>>,
-    count: usize
+/// A hash map using a Swiss-table-style layout: a control byte per slot lets a probe reject most
+/// non-matching slots with a single SWAR comparison over a group of them before ever touching a
+/// key, with quadratic probing across groups on a miss.
+pub struct HashMap<K: Hash + Eq, V: Eq + Clone, S: BuildHasher = RandomState> {
+    ht: RawTable<K, V>,
+    count: usize,
+    resize_at: usize,
+    /// How many more `CTRL_EMPTY` slots may be filled before a rehash; it starts at `resize_at`,
+    /// not at the number of slots, so it never lets every `CTRL_EMPTY` slot be used. Unlike
+    /// `resize_at`, this is charged against by `Deleted` tombstones too and never recovers one as
+    /// slots get removed, so a table can exhaust it well before `count` reaches `resize_at` under
+    /// heavy insert/remove churn. Reaching zero forces a rehash (see `resize`), which is what
+    /// bounds a probe sequence to the table's actual size instead of spinning through a table that
+    /// is full of tombstones with no `Empty` slot left to stop a miss.
+    growth_left: usize,
+    hash_builder: S
 }
 
-#[derive(Clone, PartialEq, Debug)]
-enum CellState {
-    Empty,
-    Filled,
-    Deleted
+/// Owns the raw, fixed-size backing allocation of a [`HashMap`]: a control byte per slot
+/// ([`CTRL_EMPTY`]/[`CTRL_DELETED`]/a hash fragment) in its own array, alongside a parallel array
+/// of `raw_capacity` `Item<K, V>` slots, both allocated and freed directly through the global
+/// allocator so construction can fail gracefully instead of aborting.
+///
+/// Only slots whose control byte [`is_full`] hold an initialized `Item`; the rest are raw,
+/// uninitialized memory and must never be read as a `K`/`V` or passed to `drop_in_place`.
+struct RawTable<K, V: Eq + Clone> {
+    ctrl: NonNull<u8>,
+    items: NonNull<Item<K, V>>,
+    raw_capacity: usize
+}
+
+fn layout_for<K, V: Eq + Clone>(raw_capacity: usize) -> Result<Layout, TryReserveError> {
+    let elem_size = mem::size_of::<Item<K, V>>();
+    let align = mem::align_of::<Item<K, V>>();
+
+    let num_bytes = raw_capacity
+        .checked_mul(elem_size)
+        .ok_or(TryReserveError::CapacityOverflow)?;
+
+    Layout::from_size_align(num_bytes, align).map_err(|_| TryReserveError::CapacityOverflow)
 }
 
-impl<V: Eq + Clone> HashMap<V> {
+fn ctrl_layout_for(raw_capacity: usize) -> Result<Layout, TryReserveError> {
+    Layout::array::<u8>(raw_capacity).map_err(|_| TryReserveError::CapacityOverflow)
+}
+
+impl<K, V: Eq + Clone> RawTable<K, V> {
+    fn empty() -> Self {
+        RawTable { ctrl: NonNull::dangling(), items: NonNull::dangling(), raw_capacity: 0 }
+    }
+
+    fn try_new(raw_capacity: usize) -> Result<Self, TryReserveError> {
+        if raw_capacity == 0 {
+            return Ok(RawTable::empty());
+        }
+
+        let ctrl_layout = ctrl_layout_for(raw_capacity)?;
+        let ctrl = unsafe { alloc::alloc(ctrl_layout) };
+        let ctrl = NonNull::new(ctrl).ok_or(TryReserveError::AllocError { layout: ctrl_layout })?;
+        unsafe { ptr::write_bytes(ctrl.as_ptr(), CTRL_EMPTY, raw_capacity); }
+
+        let items_layout = layout_for::<K, V>(raw_capacity)?;
+        let items = unsafe { alloc::alloc(items_layout) } as *mut Item<K, V>;
+        let items = match NonNull::new(items) {
+            Some(items) => items,
+            None => {
+                unsafe { alloc::dealloc(ctrl.as_ptr(), ctrl_layout); }
+                return Err(TryReserveError::AllocError { layout: items_layout });
+            }
+        };
+
+        Ok(RawTable { ctrl, items, raw_capacity })
+    }
+
+    fn capacity(&self) -> usize {
+        self.raw_capacity
+    }
+
+    fn num_groups(&self) -> usize {
+        self.raw_capacity / GROUP_SIZE
+    }
+
+    fn ctrl_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ctrl.as_ptr(), self.raw_capacity) }
+    }
+
+    fn ctrl(&self, index: usize) -> u8 {
+        unsafe { *self.ctrl.as_ptr().add(index) }
+    }
+
+    fn set_ctrl(&mut self, index: usize, byte: u8) {
+        unsafe { *self.ctrl.as_ptr().add(index) = byte; }
+    }
+
+    /// Loads the `GROUP_SIZE` control bytes of `group` as a little-endian `u64`, so
+    /// [`match_byte`] can test all of them with a single SWAR comparison. `raw_capacity` is
+    /// always a multiple of `GROUP_SIZE`, so a group-aligned load never runs past the array.
+    fn load_group(&self, group: usize) -> u64 {
+        unsafe {
+            let ptr = self.ctrl.as_ptr().add(group * GROUP_SIZE) as *const [u8; GROUP_SIZE];
+            u64::from_le_bytes(*ptr)
+        }
+    }
+
+    fn as_slice(&self) -> &[Item<K, V>] {
+        unsafe { slice::from_raw_parts(self.items.as_ptr(), self.raw_capacity) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Item<K, V>] {
+        unsafe { slice::from_raw_parts_mut(self.items.as_ptr(), self.raw_capacity) }
+    }
+
+    /// Writes a freshly-inserted item into a slot already known to be non-`Full` (`Empty` or
+    /// `Deleted`), without dropping whatever uninitialized or already-moved-out bytes were there.
+    fn write_item(&mut self, index: usize, item: Item<K, V>) {
+        unsafe { ptr::write(self.items.as_ptr().add(index), item); }
+    }
+
+    fn iter(&self) -> Iter<'_, K, V> {
+        Iter { ctrl: self.ctrl_slice().iter(), items: self.as_slice().iter() }
+    }
+
+    fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        // `ctrl` and `items` are separate allocations, so borrowing one immutably and the other
+        // mutably at the same time is sound even though they hang off the same `&mut self`.
+        let ctrl = unsafe { slice::from_raw_parts(self.ctrl.as_ptr(), self.raw_capacity) };
+        IterMut { ctrl: ctrl.iter(), items: self.as_mut_slice().iter_mut() }
+    }
+
+    /// Consumes the table, returning its full entries. Non-full slots are left untouched since
+    /// they were never initialized.
+    fn drain_filled(self) -> Vec<(K, V)> {
+        let table = ManuallyDrop::new(self);
+        let mut out = Vec::new();
+
+        if table.raw_capacity > 0 {
+            unsafe {
+                for i in 0..table.raw_capacity {
+                    if is_full(*table.ctrl.as_ptr().add(i)) {
+                        let item = ptr::read(table.items.as_ptr().add(i));
+                        out.push((item.key, item.value));
+                    }
+                }
+
+                let ctrl_layout = ctrl_layout_for(table.raw_capacity)
+                    .expect("raw_capacity was already allocated with this layout");
+                alloc::dealloc(table.ctrl.as_ptr(), ctrl_layout);
+
+                let items_layout = layout_for::<K, V>(table.raw_capacity)
+                    .expect("raw_capacity was already allocated with this layout");
+                alloc::dealloc(table.items.as_ptr() as *mut u8, items_layout);
+            }
+        }
+
+        out
+    }
+
+    /// Takes ownership of the `Full` item at `index`, marking the slot `Deleted`. The caller
+    /// must ensure the slot was actually full.
+    fn take(&mut self, index: usize) -> Item<K, V> {
+        let item = unsafe { ptr::read(self.items.as_ptr().add(index)) };
+        self.set_ctrl(index, CTRL_DELETED);
+        item
+    }
+}
+
+impl<K, V: Eq + Clone> Deref for RawTable<K, V> {
+    type Target = [Item<K, V>];
+
+    fn deref(&self) -> &[Item<K, V>] {
+        self.as_slice()
+    }
+}
+
+impl<K, V: Eq + Clone> DerefMut for RawTable<K, V> {
+    fn deref_mut(&mut self) -> &mut [Item<K, V>] {
+        self.as_mut_slice()
+    }
+}
+
+impl<K, V: Eq + Clone> Drop for RawTable<K, V> {
+    fn drop(&mut self) {
+        if self.raw_capacity == 0 {
+            return;
+        }
+
+        unsafe {
+            for i in 0..self.raw_capacity {
+                if is_full(*self.ctrl.as_ptr().add(i)) {
+                    ptr::drop_in_place(self.items.as_ptr().add(i));
+                }
+            }
+
+            let ctrl_layout = ctrl_layout_for(self.raw_capacity)
+                .expect("raw_capacity was already allocated with this layout");
+            alloc::dealloc(self.ctrl.as_ptr(), ctrl_layout);
+
+            let items_layout = layout_for::<K, V>(self.raw_capacity)
+                .expect("raw_capacity was already allocated with this layout");
+            alloc::dealloc(self.items.as_ptr() as *mut u8, items_layout);
+        }
+    }
+}
+
+/// Outcome of walking a probe sequence: either the key's slot, or the first reusable
+/// (`Empty`/`Deleted`) slot found along it, which is where insertion should write.
+enum ProbeResult {
+    Found(usize),
+    Vacant(usize)
+}
+
+impl<K: Hash + Eq, V: Eq + Clone> HashMap<K, V, RandomState> {
     /// Creates an empty `HashMap`.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
     /// use mk_collections::HashMap;
     ///
-    /// let mut map = HashMap::<i32>::new();
+    /// let mut map = HashMap::<i32, i32>::new();
     /// assert_eq!(map.capacity(), 0);
     /// ```
-    pub fn new() -> HashMap<V> {
+    pub fn new() -> HashMap<K, V, RandomState> {
        HashMap::with_capacity(0)
     }
 
     /// Creates an empty `HashMap` with the specified capacity.
-    /// 
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::<i32, i32>::with_capacity(10);
+    /// assert!(map.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> HashMap<K, V, RandomState> {
+        HashMap::with_capacity_and_hasher(capacity, RandomState::default())
+    }
+
+    /// Creates an empty `HashMap` with the specified capacity, returning an error instead of
+    /// panicking if the backing table could not be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let map = HashMap::<i32, i32>::try_with_capacity(10);
+    /// assert!(map.is_ok());
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<HashMap<K, V, RandomState>, TryReserveError> {
+        HashMap::try_with_capacity_and_hasher(capacity, RandomState::default())
+    }
+}
+
+impl<K: Hash + Eq, V: Eq + Clone, S: BuildHasher> HashMap<K, V, S> {
+    /// Creates an empty `HashMap` which will use the given hash builder to hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::RandomState;
+    /// use mk_collections::HashMap;
+    ///
+    /// let s = RandomState::new();
+    /// let mut map = HashMap::<i32, i32, _>::with_hasher(s);
+    /// assert!(map.insert(3, 7).is_ok());
+    /// ```
+    pub fn with_hasher(hash_builder: S) -> HashMap<K, V, S> {
+        HashMap::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    /// Creates an empty `HashMap` with the specified capacity, using the given hash builder to
+    /// hash keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::RandomState;
+    /// use mk_collections::HashMap;
+    ///
+    /// let s = RandomState::new();
+    /// let mut map = HashMap::<i32, i32, _>::with_capacity_and_hasher(10, s);
+    /// assert!(map.capacity() >= 10);
+    /// ```
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> HashMap<K, V, S> {
+        HashMap::try_with_capacity_and_hasher(capacity, hash_builder)
+            .expect("failed to allocate hash table")
+    }
+
+    /// Creates an empty `HashMap` with the specified capacity and hash builder, returning an
+    /// error instead of panicking if the backing table could not be allocated.
+    ///
     /// # Examples
     ///
     /// ```
+    /// use mk_collections::RandomState;
     /// use mk_collections::HashMap;
     ///
-    /// let mut map = HashMap::<i32>::with_capacity(10);
-    /// assert_eq!(map.capacity(), 10);
+    /// let map = HashMap::<i32, i32, _>::try_with_capacity_and_hasher(10, RandomState::new());
+    /// assert!(map.is_ok());
     /// ```
-    pub fn with_capacity(capacity: usize) -> HashMap<V> {
-        HashMap { 
-            ht: init_table(capacity),
-            count: 0
+    pub fn try_with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Result<HashMap<K, V, S>, TryReserveError> {
+        let raw_capacity = raw_capacity_for(capacity)?;
+
+        Ok(HashMap {
+            ht: RawTable::try_new(raw_capacity)?,
+            count: 0,
+            resize_at: resize_at(raw_capacity),
+            growth_left: resize_at(raw_capacity),
+            hash_builder
+        })
+    }
+
+    /// Reserves capacity for at least `additional` more elements, growing the table if needed.
+    /// Returns an error instead of panicking if the larger table could not be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert!(map.try_reserve(100).is_ok());
+    /// assert!(map.capacity() >= 100);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self.count.checked_add(additional).ok_or(TryReserveError::CapacityOverflow)?;
+
+        if required <= self.resize_at {
+            return Ok(());
         }
+
+        let mut raw_capacity = if self.ht.capacity() == 0 { MIN_CAPACITY } else { self.ht.capacity() };
+        while resize_at(raw_capacity) < required {
+            raw_capacity = raw_capacity.checked_mul(2).ok_or(TryReserveError::CapacityOverflow)?;
+        }
+
+        self.grow_to(raw_capacity)
     }
 
-    /// Gets capacity 
+    /// Returns the number of elements the map can hold before it needs to resize.
+    ///
+    /// This is the usable capacity under the map's load factor, not the raw number of slots
+    /// backing the table.
     pub fn capacity(&self) -> usize {
-        self.ht.capacity()
+        self.resize_at
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert_eq!(map.len(), 0);
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.is_empty());
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert!(!map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert_eq!(map.iter().count(), 1);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.ht.iter()
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with mutable references to
+    /// the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, 1).is_ok());
+    /// for (_, value) in map.iter_mut() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(*map.find(&3).unwrap(), 2);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.ht.iter_mut()
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert_eq!(map.keys().next(), Some(&3));
+    /// ```
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, "a").is_ok());
+    /// assert_eq!(map.values().next(), Some(&"a"));
+    /// ```
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values mutably in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// assert!(map.insert(3, 1).is_ok());
+    /// for value in map.values_mut() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(*map.find(&3).unwrap(), 2);
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut { inner: self.iter_mut() }
     }
 
     /// Returns a reference to the value corresponding to the key, or [`None`] if it didn't found in the map.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -75,10 +809,10 @@ impl<V: Eq + Clone> HashMap<V> {
     ///
     /// let mut map = HashMap::new();
     /// assert!(map.insert(3, "a").is_ok());
-    /// assert_eq!(*map.find(3).unwrap(), "a");
-    /// assert!(map.find(4).is_none());
+    /// assert_eq!(*map.find(&3).unwrap(), "a");
+    /// assert!(map.find(&4).is_none());
     /// ```
-    pub fn find(&self, key: i32) -> Option<&V> {
+    pub fn find(&self, key: &K) -> Option<&V> {
         if let Some(found) = self.find_index(key) {
             return Some(&self.ht[found].value);
         } else {
@@ -87,10 +821,10 @@ impl<V: Eq + Clone> HashMap<V> {
     }
 
     /// Inserts a key-value pair into the map.
-    /// 
+    ///
     /// If the map already have the key present, it returns error result `DupErr`.
     /// To modify the value of already present key use the put method.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -98,22 +832,24 @@ impl<V: Eq + Clone> HashMap<V> {
     ///
     /// let mut map = HashMap::new();
     /// assert!(map.insert(3, "a").is_ok());
-    /// assert_eq!(*map.find(3).unwrap(), "a");
+    /// assert_eq!(*map.find(&3).unwrap(), "a");
     /// ```
-    pub fn insert(&mut self, key: i32, value: V) -> Result<(), DupErr> {
-        if self.count == self.ht.capacity() {
+    pub fn insert(&mut self, key: K, value: V) -> Result<(), DupErr<K>> {
+        if self.count >= self.resize_at || self.growth_left == 0 {
             self.resize();
         }
 
         let res = self.insert_inner(key, value);
-        
-        self.count += 1;
+
+        if res.is_ok() {
+            self.count += 1;
+        }
 
         res
     }
 
     /// Returns `true` if the map have this key present, and `false` - otherwise.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -122,17 +858,17 @@ impl<V: Eq + Clone> HashMap<V> {
     /// let mut map = HashMap::new();
     /// assert!(map.insert(3, "a").is_ok());
     /// assert!(map.insert(5, "a").is_ok());
-    /// 
-    /// assert!(map.contains_key(3));
-    /// assert!(map.contains_key(5));
+    ///
+    /// assert!(map.contains_key(&3));
+    /// assert!(map.contains_key(&5));
     /// ```
-    pub fn contains_key(&self, key: i32) -> bool {
+    pub fn contains_key(&self, key: &K) -> bool {
         self.find_index(key).is_some()
     }
 
     /// Updates the value if key is present in the map or inserts the new key-value pair if it's not.
     /// If it updates the old value will be returned, otherwise - [`None`].
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -142,18 +878,20 @@ impl<V: Eq + Clone> HashMap<V> {
     /// assert!(map.insert(3, "a").is_ok());
     /// assert_eq!(map.put(3, "b").unwrap(), "a");
     /// ```
-    pub fn put(&mut self, key: i32, value: V) -> Option<V> {
-        if let Some(index) = self.find_index(key) {
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(index) = self.find_index(&key) {
             Some(mem::replace(&mut self.ht[index].value, value))
         } else {
-            self.insert(key, value).expect("cannot insert key-value pair");
-            None
+            match self.insert(key, value) {
+                Ok(()) => None,
+                Err(_) => unreachable!("find_index just reported this key as absent")
+            }
         }
     }
 
     /// Removes a key from the map, returning the value at the key if the key
     /// was previously in the map.
-    /// 
+    ///
     /// # Examples
     ///
     /// ```
@@ -161,115 +899,379 @@ impl<V: Eq + Clone> HashMap<V> {
     ///
     /// let mut map = HashMap::new();
     /// assert!(map.insert(3, "a").is_ok());
-    /// 
-    /// assert_eq!(*map.remove(3).unwrap(), "a");
-    /// assert!(map.remove(3).is_none());
-    /// ```
-    pub fn remove(&mut self, key: i32) -> Option<&V> {
-        if let Some(index) = self.find_index(key) {
-            self.ht[index].state = CellState::Deleted;
-            self.count -= 1;
-        
-            return Some(&self.ht[index].value);
-        } else {
-            return None;
+    ///
+    /// assert_eq!(map.remove(&3).unwrap(), "a");
+    /// assert!(map.remove(&3).is_none());
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.find_index(key)?;
+        self.count -= 1;
+
+        Some(self.ht.take(index).value)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place update-or-insert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mk_collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// *map.entry(3).or_insert(0) += 1;
+    /// *map.entry(3).or_insert(0) += 1;
+    /// assert_eq!(*map.find(&3).unwrap(), 2);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        if self.count >= self.resize_at || self.growth_left == 0 {
+            self.resize();
+        }
+
+        match self.probe(&key) {
+            ProbeResult::Found(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            ProbeResult::Vacant(index) => Entry::Vacant(VacantEntry { map: self, key, index })
         }
     }
 
-    fn find_index(&self, key: i32) -> Option<usize> {
-        let i = self.index(key);
-        let item = &self.ht[i];
+    /// Walks `key`'s probe sequence one group at a time, returning either its slot or the first
+    /// reusable (`Empty`/`Deleted`) slot found along the way. Each group's control bytes are
+    /// compared against `key`'s hash fragment in one SWAR pass, so a group with no matching
+    /// fragment and no empty slot is rejected without touching a single key; only a fragment
+    /// match is ever checked against the real key, guarding against the 1-in-128 false positive.
+    /// Shared by `find_index`, `insert_inner` and `entry` so a read-modify-write only probes the
+    /// table once.
+    fn probe(&self, key: &K) -> ProbeResult {
+        if self.ht.capacity() == 0 {
+            return ProbeResult::Vacant(0);
+        }
+
+        let hash = self.hash(key);
+        let fragment = fragment(hash);
+        let group_mask = self.ht.num_groups() - 1;
+        let mut seq = ProbeSeq::new((hash >> 7) as usize, group_mask);
+        let mut first_vacant = None;
 
-        if item.key == key && item.state == CellState::Filled {
-            return Some(i);
-        } else {
-            let mut index = self.next_index(i);
-            while index != i && {
-                        let item = &self.ht[index];
-                        ((item.state == CellState::Filled && item.key != key) 
-                            || item.state == CellState::Deleted)
-                    } {
-                index = self.next_index(index);
+        loop {
+            let group = self.ht.load_group(seq.group);
+            let base = seq.group * GROUP_SIZE;
+
+            for lane in match_lanes(match_byte(group, fragment)) {
+                let index = base + lane;
+                if self.ht[index].key == *key {
+                    return ProbeResult::Found(index);
+                }
             }
 
-            if index == i || self.ht[index].state == CellState::Empty {
-                return Option::None;
-            } else {
-                return Option::Some(index)
+            if first_vacant.is_none() {
+                if let Some(lane) = match_lanes(match_byte(group, CTRL_DELETED)).next() {
+                    first_vacant = Some(base + lane);
+                }
             }
+
+            if let Some(lane) = match_lanes(match_byte(group, CTRL_EMPTY)).next() {
+                return ProbeResult::Vacant(first_vacant.unwrap_or(base + lane));
+            }
+
+            seq.advance(group_mask);
         }
     }
 
-    fn insert_inner(&mut self, key: i32, value: V) -> Result<(), DupErr> {
-        let index = self.index(key);
-
-        if self.ht[index].state == CellState::Filled {
-            let item = &self.ht[index];
-            if item.key == key {
-                return Err(DupErr { key });
-            } else {
-                let mut index = self.next_index(index);
-                while self.ht[index].state == CellState::Filled {
-                    if self.ht[index].key == key {
-                        return Err(DupErr { key });
-                    }
-                    index = self.next_index(index);
-                }
+    fn find_index(&self, key: &K) -> Option<usize> {
+        match self.probe(key) {
+            ProbeResult::Found(index) => Some(index),
+            ProbeResult::Vacant(_) => None
+        }
+    }
 
-                self.put_to_index(index, key, value);
+    fn insert_inner(&mut self, key: K, value: V) -> Result<(), DupErr<K>> {
+        match self.probe(&key) {
+            ProbeResult::Found(_) => Err(DupErr { key }),
+            ProbeResult::Vacant(index) => {
+                self.write_at(index, key, value);
+                Ok(())
             }
+        }
+    }
+
+    fn write_at(&mut self, index: usize, key: K, value: V) {
+        if self.ht.ctrl(index) == CTRL_EMPTY {
+            self.growth_left -= 1;
+        }
+
+        let hash = self.hash(&key);
+        self.ht.set_ctrl(index, fragment(hash));
+        self.ht.write_item(index, Item { key, value });
+    }
+
+    /// Grows the table, or — if `count` is still below `resize_at` and it's only `Deleted`
+    /// tombstones that exhausted `growth_left` — rehashes into a newly allocated table of the
+    /// same capacity. Either
+    /// way every live item is reinserted into a table with no tombstones, so `growth_left` is
+    /// restored and a probe sequence is guaranteed to hit a true `Empty` slot again.
+    fn resize(&mut self) {
+        let raw_capacity = if self.count >= self.resize_at || self.ht.capacity() == 0 {
+            if self.ht.capacity() == 0 { MIN_CAPACITY } else { self.ht.capacity() * 2 }
         } else {
-            self.put_to_index(index, key, value);
+            self.ht.capacity()
+        };
+
+        self.grow_to(raw_capacity).expect("failed to allocate hash table");
+    }
+
+    fn grow_to(&mut self, raw_capacity: usize) -> Result<(), TryReserveError> {
+        let ht = RawTable::try_new(raw_capacity)?;
+
+        let old_ht = mem::replace(&mut self.ht, ht);
+        self.resize_at = resize_at(raw_capacity);
+        self.growth_left = self.resize_at;
+
+        for (key, value) in old_ht.drain_filled() {
+            if self.insert_inner(key, value).is_err() {
+                unreachable!("draining a table's own keys can't produce a duplicate");
+            }
         }
 
         Ok(())
     }
 
-    fn put_to_index(&mut self, index: usize, key: i32, value: V) {
-        self.ht[index] = Item { key, value, state: CellState::Filled };
+    fn hash(&self, key: &K) -> u64 {
+        let mut hasher = self.hash_builder.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This enum is constructed from the [`entry`](HashMap::entry) method on [`HashMap`].
+pub enum Entry<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>)
+}
+
+impl<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> Entry<'a, K, V, S> {
+    /// Ensures a value is in the entry by inserting `default` if empty, and returns a mutable
+    /// reference to the value in the entry.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default)
+        }
     }
 
-    fn resize(&mut self) {
-        let capacity = 
-            if self.ht.is_empty() { 1 }
-            else { self.capacity() * 2 };
+    /// Ensures a value is in the entry by inserting the result of `default` if empty, and
+    /// returns a mutable reference to the value in the entry.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default())
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential inserts.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry)
+        }
+    }
+}
+
+/// A view into an occupied entry in a [`HashMap`]. It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> {
+    map: &'a mut HashMap<K, V, S>,
+    index: usize
+}
+
+impl<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> OccupiedEntry<'a, K, V, S> {
+    /// Gets a reference to the key in the entry.
+    pub fn key(&self) -> &K {
+        &self.map.ht[self.index].key
+    }
+
+    /// Gets a reference to the value in the entry.
+    pub fn get(&self) -> &V {
+        &self.map.ht[self.index].value
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.ht[self.index].value
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.ht[self.index].value
+    }
+}
+
+/// A view into a vacant entry in a [`HashMap`]. It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> {
+    map: &'a mut HashMap<K, V, S>,
+    key: K,
+    index: usize
+}
+
+impl<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> VacantEntry<'a, K, V, S> {
+    /// Gets a reference to the key that would be used when inserting a value through `self`.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
 
-        let ht = init_table(capacity);
+    /// Sets the value of the entry, returning a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.write_at(self.index, self.key, value);
+        self.map.count += 1;
 
-        let mut old_ht = mem::replace(&mut self.ht, ht);
+        &mut self.map.ht[self.index].value
+    }
+}
 
-        for item in old_ht.drain(..)
-                    .enumerate()
-                    .filter(|(_, item)| item.state == CellState::Filled)
-                    .map(|(_, item)| item) {
-            self.insert_inner(item.key, item.value).unwrap();
+/// An iterator over the key-value pairs of a `HashMap`, obtained from [`HashMap::iter`].
+pub struct Iter<'a, K, V: Eq + Clone> {
+    ctrl: slice::Iter<'a, u8>,
+    items: slice::Iter<'a, Item<K, V>>
+}
+
+impl<'a, K, V: Eq + Clone> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let (Some(&ctrl), Some(item)) = (self.ctrl.next(), self.items.next()) {
+            if is_full(ctrl) {
+                return Some((&item.key, &item.value));
+            }
         }
+        None
+    }
+}
+
+impl<'a, K, V: Eq + Clone> FusedIterator for Iter<'a, K, V> {}
+
+/// A mutable iterator over the key-value pairs of a `HashMap`, obtained from [`HashMap::iter_mut`].
+pub struct IterMut<'a, K, V: Eq + Clone> {
+    ctrl: slice::Iter<'a, u8>,
+    items: slice::IterMut<'a, Item<K, V>>
+}
+
+impl<'a, K, V: Eq + Clone> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let (Some(&ctrl), Some(item)) = (self.ctrl.next(), self.items.next()) {
+            if is_full(ctrl) {
+                return Some((&item.key, &mut item.value));
+            }
+        }
+        None
+    }
+}
+
+impl<'a, K, V: Eq + Clone> FusedIterator for IterMut<'a, K, V> {}
+
+/// An owning iterator over the key-value pairs of a `HashMap`, obtained from its [`IntoIterator`] impl.
+pub struct IntoIter<K, V: Eq + Clone> {
+    inner: std::vec::IntoIter<(K, V)>
+}
+
+impl<K, V: Eq + Clone> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<K, V: Eq + Clone> FusedIterator for IntoIter<K, V> {}
+
+/// An iterator over the keys of a `HashMap`, obtained from [`HashMap::keys`].
+pub struct Keys<'a, K, V: Eq + Clone> {
+    inner: Iter<'a, K, V>
+}
+
+impl<'a, K, V: Eq + Clone> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
     }
+}
+
+impl<'a, K, V: Eq + Clone> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values of a `HashMap`, obtained from [`HashMap::values`].
+pub struct Values<'a, K, V: Eq + Clone> {
+    inner: Iter<'a, K, V>
+}
 
-    fn index(&self, key: i32) -> usize {
-        key as usize % self.ht.capacity()
+impl<'a, K, V: Eq + Clone> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
     }
+}
 
-    fn next_index(&self, index: usize) -> usize {
-        (index + 1) % self.ht.capacity()
+impl<'a, K, V: Eq + Clone> FusedIterator for Values<'a, K, V> {}
+
+/// A mutable iterator over the values of a `HashMap`, obtained from [`HashMap::values_mut`].
+pub struct ValuesMut<'a, K, V: Eq + Clone> {
+    inner: IterMut<'a, K, V>
+}
+
+impl<'a, K, V: Eq + Clone> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
     }
-} 
+}
 
-fn init_table<V: Eq + Clone>(capacity: usize) -> Vec<Item<V>> {
-    
-    let align = mem::align_of::<Item<V>>();
-    let elem_size = mem::size_of::<Item<V>>();
+impl<'a, K, V: Eq + Clone> FusedIterator for ValuesMut<'a, K, V> {}
 
-    let num_bytes = capacity * elem_size;
-    let ptr = unsafe { alloc::alloc(
-        Layout::from_size_align(num_bytes, align)
-            .expect("Bad layout")) };
+impl<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
 
-    let mut res = unsafe { Vec::from_raw_parts(ptr as *mut Item<V>, capacity, capacity) };
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
 
-    for i in 0..capacity {
-        res[i].state = CellState::Empty;
+impl<'a, K: Hash + Eq, V: Eq + Clone, S: BuildHasher> IntoIterator for &'a mut HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
+}
 
-    res
+impl<K: Hash + Eq, V: Eq + Clone, S: BuildHasher> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let ht = mem::replace(&mut self.ht, RawTable::empty());
+        IntoIter { inner: ht.drain_filled().into_iter() }
+    }
+}
+
+impl<K: Hash + Eq, V: Eq + Clone, S: BuildHasher + Default> FromIterator<(K, V)> for HashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = HashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V: Eq + Clone, S: BuildHasher> Extend<(K, V)> for HashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
 }